@@ -0,0 +1,171 @@
+use secp256k1::hashes::sha256;
+use secp256k1::schnorr::Signature;
+use secp256k1::{Message, Secp256k1, VerifyOnly, XOnlyPublicKey};
+use std::io;
+use std::str::FromStr;
+
+/// Verifies that an `Acceleration` was authorized by the holder of a
+/// configured x-only secp256k1 key, so a tampered or forged log entry can't
+/// cause us to prioritise an arbitrary transaction.
+pub struct Authorizer {
+    secp: Secp256k1<VerifyOnly>,
+    pubkey: XOnlyPublicKey,
+}
+
+impl Authorizer {
+    /// Reads the authorizing public key from `AUTHORIZING_PUBKEY`, a 64-character
+    /// hex-encoded x-only (32-byte) secp256k1 pubkey.
+    pub fn from_env() -> io::Result<Self> {
+        let hex_key = std::env::var("AUTHORIZING_PUBKEY").map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "AUTHORIZING_PUBKEY must be set to a hex x-only secp256k1 public key",
+            )
+        })?;
+        let pubkey = XOnlyPublicKey::from_str(&hex_key)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        Ok(Self {
+            secp: Secp256k1::verification_only(),
+            pubkey,
+        })
+    }
+
+    #[cfg(test)]
+    fn with_pubkey(pubkey: XOnlyPublicKey) -> Self {
+        Self {
+            secp: Secp256k1::verification_only(),
+            pubkey,
+        }
+    }
+
+    /// Verifies `signature_hex` is a valid BIP340 Schnorr signature over the
+    /// canonical message for `(chain, txid, fee_delta, added)`, made by the
+    /// configured authorizing key.
+    pub fn verify(
+        &self,
+        chain: &str,
+        txid: &str,
+        fee_delta: i64,
+        added: Option<u64>,
+        signature_hex: &str,
+    ) -> bool {
+        let Ok(sig_bytes) = hex::decode(signature_hex) else {
+            return false;
+        };
+        let Ok(signature) = Signature::from_slice(&sig_bytes) else {
+            return false;
+        };
+        let message = Message::from_hashed_data::<sha256::Hash>(&canonical_message_bytes(
+            chain, txid, fee_delta, added,
+        ));
+
+        self.secp
+            .verify_schnorr(&signature, &message, &self.pubkey)
+            .is_ok()
+    }
+}
+
+/// `len(chain) || chain || len(txid) || txid || feeDelta || added`, with every
+/// length and numeric field in little-endian, hashed to a 32-byte BIP340
+/// message. `chain` is included so a signature authorized for one chain's
+/// node can't be replayed against another chain's backend for the same
+/// txid, and each variable-length field is length-prefixed so the
+/// `chain`/`txid` boundary can't be shifted to produce a different,
+/// still-validly-signed `(chain, txid)` pair from the same byte string.
+fn canonical_message_bytes(chain: &str, txid: &str, fee_delta: i64, added: Option<u64>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(8 + chain.len() + 8 + txid.len() + 8 + 8);
+    bytes.extend_from_slice(&(chain.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(chain.as_bytes());
+    bytes.extend_from_slice(&(txid.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(txid.as_bytes());
+    bytes.extend_from_slice(&fee_delta.to_le_bytes());
+    bytes.extend_from_slice(&added.unwrap_or(0).to_le_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secp256k1::Keypair;
+
+    fn sign(
+        secp: &Secp256k1<secp256k1::All>,
+        keypair: &Keypair,
+        chain: &str,
+        txid: &str,
+        fee_delta: i64,
+        added: Option<u64>,
+    ) -> String {
+        let message =
+            Message::from_hashed_data::<sha256::Hash>(&canonical_message_bytes(
+                chain, txid, fee_delta, added,
+            ));
+        let signature = secp.sign_schnorr_no_aux_rand(&message, keypair);
+        hex::encode(signature.as_ref())
+    }
+
+    #[test]
+    fn accepts_valid_signature() {
+        let secp = Secp256k1::new();
+        let keypair = Keypair::new(&secp, &mut rand_from_seed(1));
+        let (pubkey, _) = keypair.x_only_public_key();
+        let authorizer = Authorizer::with_pubkey(pubkey);
+
+        let sig = sign(&secp, &keypair, "bitcoin", "deadbeef", 500, Some(42));
+        assert!(authorizer.verify("bitcoin", "deadbeef", 500, Some(42), &sig));
+    }
+
+    #[test]
+    fn rejects_signature_replayed_on_a_different_chain() {
+        let secp = Secp256k1::new();
+        let keypair = Keypair::new(&secp, &mut rand_from_seed(2));
+        let (pubkey, _) = keypair.x_only_public_key();
+        let authorizer = Authorizer::with_pubkey(pubkey);
+
+        let sig = sign(&secp, &keypair, "bitcoin", "deadbeef", 500, Some(42));
+        assert!(!authorizer.verify("litecoin", "deadbeef", 500, Some(42), &sig));
+    }
+
+    #[test]
+    fn rejects_tampered_fee_delta() {
+        let secp = Secp256k1::new();
+        let keypair = Keypair::new(&secp, &mut rand_from_seed(3));
+        let (pubkey, _) = keypair.x_only_public_key();
+        let authorizer = Authorizer::with_pubkey(pubkey);
+
+        let sig = sign(&secp, &keypair, "bitcoin", "deadbeef", 500, Some(42));
+        assert!(!authorizer.verify("bitcoin", "deadbeef", 999, Some(42), &sig));
+    }
+
+    #[test]
+    fn rejects_boundary_shifted_chain_and_txid() {
+        // Without length-prefixing, chain="bitcoin" + txid="Xdeadbeef" hashes
+        // to the same bytes as chain="bitcoinX" + txid="deadbeef": a forger
+        // holding a valid (chain, txid, signature) tuple could shift the
+        // boundary to mint a different, still-validly-signed (chain, txid).
+        let secp = Secp256k1::new();
+        let keypair = Keypair::new(&secp, &mut rand_from_seed(5));
+        let (pubkey, _) = keypair.x_only_public_key();
+        let authorizer = Authorizer::with_pubkey(pubkey);
+
+        let sig = sign(&secp, &keypair, "bitcoin", "Xdeadbeef", 500, Some(42));
+        assert!(!authorizer.verify("bitcoinX", "deadbeef", 500, Some(42), &sig));
+    }
+
+    #[test]
+    fn rejects_garbage_signature() {
+        let secp = Secp256k1::new();
+        let keypair = Keypair::new(&secp, &mut rand_from_seed(4));
+        let (pubkey, _) = keypair.x_only_public_key();
+        let authorizer = Authorizer::with_pubkey(pubkey);
+
+        assert!(!authorizer.verify("bitcoin", "deadbeef", 500, Some(42), "not-hex"));
+    }
+
+    /// Deterministic 32-byte "randomness" for keypair generation in tests.
+    fn rand_from_seed(seed: u8) -> impl rand::RngCore {
+        use rand::SeedableRng;
+        rand::rngs::StdRng::from_seed([seed; 32])
+    }
+}
@@ -0,0 +1,73 @@
+use crate::rpc::{RpcAuth, RpcConfig};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+/// One chain's RPC endpoint as it appears in `chains.json`.
+#[derive(Deserialize, Debug, Clone)]
+struct ChainEntry {
+    host: String,
+    port: u16,
+    #[serde(default)]
+    wallet: Option<String>,
+    #[serde(default)]
+    cookie_file: Option<String>,
+    #[serde(default)]
+    user: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+}
+
+impl ChainEntry {
+    fn into_rpc_config(self) -> io::Result<RpcConfig> {
+        let auth = if let Some(cookie_path) = self.cookie_file {
+            RpcAuth::CookieFile(fs::read_to_string(cookie_path)?.trim().to_string())
+        } else {
+            RpcAuth::UserPass(self.user.unwrap_or_default(), self.password.unwrap_or_default())
+        };
+
+        Ok(RpcConfig {
+            host: self.host,
+            port: self.port,
+            wallet: self.wallet,
+            auth,
+        })
+    }
+}
+
+/// Maps a chain name (`"bitcoin"`, `"litecoin"`, ...) to the RPC endpoint
+/// that should handle accelerations for it.
+pub struct ChainsConfig {
+    chains: HashMap<String, RpcConfig>,
+}
+
+impl ChainsConfig {
+    const DEFAULT_PATH: &'static str = "chains.json";
+
+    /// Loads `chains.json` if present; otherwise falls back to a single
+    /// `"bitcoin"` entry built from the environment, preserving the
+    /// single-chain behavior this crate started with.
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(Self::DEFAULT_PATH) {
+            Ok(contents) => {
+                let raw: HashMap<String, ChainEntry> = serde_json::from_str(&contents)?;
+                let mut chains = HashMap::new();
+                for (name, entry) in raw {
+                    chains.insert(name, entry.into_rpc_config()?);
+                }
+                Ok(Self { chains })
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let mut chains = HashMap::new();
+                chains.insert("bitcoin".to_string(), RpcConfig::from_env()?);
+                Ok(Self { chains })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn get(&self, chain: &str) -> Option<&RpcConfig> {
+        self.chains.get(chain)
+    }
+}
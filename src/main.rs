@@ -1,10 +1,38 @@
-use chrono::{DateTime, Utc};
+mod auth;
+mod config;
+mod rpc;
+mod sinks;
+
+use auth::Authorizer;
+use chrono::{DateTime, Duration, Utc};
+use config::ChainsConfig;
+use rpc::{RpcCallError, RpcClient};
 use serde::{Deserialize, Serialize};
-use std::fs::{self, File};
-use std::io::{self, Write};
-use std::process::Command;
+use serde_json::json;
+use sinks::{AccelerationEvent, EventStatus, Sink};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
 use std::time::SystemTime;
 
+fn default_chain() -> String {
+    "bitcoin".to_string()
+}
+
+const BASE_BACKOFF_SECS: i64 = 30;
+const MAX_BACKOFF_SECS: i64 = 3600;
+
+/// How a `prioritisetransaction` attempt (or a round of reconciliation) for
+/// a single `Acceleration` has fared so far.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    #[default]
+    Pending,
+    Applied,
+    Failed,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Acceleration {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -33,6 +61,34 @@ struct Acceleration {
 
     #[serde(rename = "loggedAt")]
     logged_at: String,
+
+    /// Which chain's node this entry should be prioritised against, e.g.
+    /// `"bitcoin"` or `"litecoin"`. Defaults to `"bitcoin"` so logs written
+    /// before multi-chain support was added keep working.
+    #[serde(default = "default_chain")]
+    chain: String,
+
+    /// Hex-encoded BIP340 Schnorr signature over the canonical message built
+    /// from `chain`, `txid`, `feeDelta` and `added` (see `src/auth.rs`),
+    /// proving the party who paid for acceleration authorized this entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+
+    /// Whether this entry is still waiting to be applied, has been applied
+    /// (but may not yet be resolved), or failed and is waiting out a backoff.
+    #[serde(default)]
+    status: Status,
+
+    /// Number of failed attempts to apply this entry so far, including runs
+    /// where its chain had no configured RPC endpoint. A successful apply
+    /// does not increment it.
+    #[serde(default)]
+    attempts: u32,
+
+    /// Earliest time a failed entry may be retried. `None` for entries that
+    /// have never failed or have since been applied.
+    #[serde(rename = "nextRetryAt", default, skip_serializing_if = "Option::is_none")]
+    next_retry_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -40,85 +96,360 @@ struct AccelerationData {
     accelerations: Vec<Acceleration>,
 }
 
+/// Exponential backoff with a cap, counted from the first failed attempt.
+fn backoff_after(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(10);
+    let secs = BASE_BACKOFF_SECS.saturating_mul(1i64 << exponent);
+    Duration::seconds(secs.min(MAX_BACKOFF_SECS))
+}
+
 fn main() -> io::Result<()> {
     let json_path = "acceleration-logs.json";
-    let log_path = "results.log";
 
     let json_content = fs::read_to_string(json_path)?;
     let mut data: AccelerationData = serde_json::from_str(&json_content)?;
 
-    let mut remaining_accelerations = Vec::new();
-    let mut log_entries = Vec::new();
-    let mut had_failure = false;
+    let chains_config = ChainsConfig::load()?;
+    let mut sinks = sinks::load_sinks()?;
+    let authorizer = Authorizer::from_env()?;
+    let now = Utc::now();
+
+    // Every entry ends up in exactly one of these three buckets:
+    // - `output`: untouched, or already decided (rejected/backing off/no config)
+    // - `to_apply`: pending (or past their backoff) entries to prioritise this run
+    // - `to_reconcile`: already-applied entries to re-check against the mempool
+    let mut output = Vec::new();
+    let mut to_apply: HashMap<String, Vec<Acceleration>> = HashMap::new();
+    let mut to_reconcile: HashMap<String, Vec<Acceleration>> = HashMap::new();
 
     for accel in data.accelerations {
-        if (accel.event_type == "legacy" || accel.event_type == "added")
+        let actionable = (accel.event_type == "legacy" || accel.event_type == "added")
             && accel.txid.is_some()
-            && accel.fee_delta.is_some()
-        {
-            let fee_delta = accel.fee_delta.unwrap();
-            let txid = accel.txid.as_ref().unwrap();
-            let command = format!(
-                "bitcoin-cli -rpcwallet=cormorant prioritisetransaction \"{txid}\" 0.0 {fee_delta}"
-            );
+            && accel.fee_delta.is_some();
+
+        if !actionable {
+            output.push(accel);
+            continue;
+        }
 
-            match Command::new("sh").arg("-c").arg(&command).output() {
-                Ok(output) if output.status.success() => {
-                    let log_entry = format!(
-                        "{}: Success - txid: {}, fee_delta: {}\n",
-                        get_timestamp(),
-                        txid,
-                        fee_delta
+        match accel.status {
+            Status::Applied => {
+                to_reconcile.entry(accel.chain.clone()).or_default().push(accel);
+            }
+            Status::Failed if accel.next_retry_at.is_some_and(|at| now < at) => {
+                // Still backing off; leave it exactly as it is.
+                output.push(accel);
+            }
+            Status::Pending | Status::Failed => {
+                let authorized = accel.signature.as_deref().is_some_and(|signature| {
+                    authorizer.verify(
+                        &accel.chain,
+                        accel.txid.as_deref().unwrap(),
+                        accel.fee_delta.unwrap(),
+                        accel.added,
+                        signature,
+                    )
+                });
+
+                if authorized {
+                    to_apply.entry(accel.chain.clone()).or_default().push(accel);
+                } else {
+                    reject(
+                        &mut sinks,
+                        &accel.chain,
+                        accel.txid.as_deref().unwrap(),
+                        accel.fee_delta.unwrap(),
                     );
-                    log_entries.push(log_entry);
+                    output.push(accel);
                 }
-                Ok(output) => {
-                    had_failure = true;
-                    let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
-                    let log_entry = format!(
-                        "{}: Failed - txid: {}, fee_delta: {}, error: {}\n",
-                        get_timestamp(),
-                        txid,
-                        fee_delta,
-                        error
-                    );
-                    log_entries.push(log_entry);
-                    remaining_accelerations.push(accel.clone());
+            }
+        }
+    }
+
+    let mut chains: Vec<String> = to_apply.keys().chain(to_reconcile.keys()).cloned().collect();
+    chains.sort();
+    chains.dedup();
+
+    for chain in chains {
+        let apply_entries = to_apply.remove(&chain).unwrap_or_default();
+        let reconcile_entries = to_reconcile.remove(&chain).unwrap_or_default();
+
+        let Some(rpc_config) = chains_config.get(&chain) else {
+            for mut accel in apply_entries {
+                emit(
+                    &mut sinks,
+                    &chain,
+                    accel.txid.as_deref().unwrap(),
+                    accel.fee_delta.unwrap(),
+                    Some("no RPC endpoint configured for this chain".to_string()),
+                );
+                mark_failed(&mut accel, now);
+                output.push(accel);
+            }
+            // Without a client we can't re-check these either; leave as-is.
+            output.extend(reconcile_entries);
+            continue;
+        };
+        let client = RpcClient::new(rpc_config.clone());
+
+        let batch: Vec<(String, serde_json::Value)> = apply_entries
+            .iter()
+            .map(|accel| {
+                let txid = accel.txid.clone().unwrap();
+                let fee_delta = accel.fee_delta.unwrap();
+                (
+                    "prioritisetransaction".to_string(),
+                    json!([txid, 0.0, fee_delta]),
+                )
+            })
+            .collect();
+
+        let mut freshly_applied = Vec::new();
+
+        if !batch.is_empty() {
+            match client.call_batch(&batch) {
+                Ok(responses) => {
+                    for (mut accel, response) in apply_entries.into_iter().zip(responses) {
+                        let txid = accel.txid.as_ref().unwrap();
+                        let fee_delta = accel.fee_delta.unwrap();
+                        match response.error {
+                            None => {
+                                emit(&mut sinks, &chain, txid, fee_delta, None);
+                                accel.status = Status::Applied;
+                                accel.next_retry_at = None;
+                                freshly_applied.push(accel);
+                            }
+                            Some(err) => {
+                                emit(&mut sinks, &chain, txid, fee_delta, Some(err.to_string()));
+                                mark_failed(&mut accel, now);
+                                output.push(accel);
+                            }
+                        }
+                    }
                 }
                 Err(e) => {
-                    had_failure = true;
-                    let log_entry = format!(
-                        "{}: Failed - txid: {}, fee_delta: {}, error: {}\n",
-                        get_timestamp(),
-                        txid,
-                        fee_delta,
-                        e
-                    );
-                    log_entries.push(log_entry);
-                    remaining_accelerations.push(accel.clone());
+                    // The whole batch failed (node unreachable, auth rejected,
+                    // malformed batch response, ...); treat nothing in it as applied.
+                    for mut accel in apply_entries {
+                        let txid = accel.txid.as_ref().unwrap();
+                        let fee_delta = accel.fee_delta.unwrap();
+                        emit(&mut sinks, &chain, txid, fee_delta, Some(e.to_string()));
+                        mark_failed(&mut accel, now);
+                        output.push(accel);
+                    }
                 }
             }
-        } else {
-            remaining_accelerations.push(accel.clone());
         }
-    }
 
-    let mut log_file = File::options().create(true).append(true).open(log_path)?;
-    for entry in log_entries {
-        log_file.write_all(entry.as_bytes())?;
-    }
+        // `reconcile_entries` were loaded straight off disk with `status:
+        // "applied"` and may have been tampered with directly in the JSON
+        // file, so re-verify their signature before letting them drive any
+        // further RPC call; `freshly_applied` entries were already verified
+        // earlier in this same run and don't need it again. Entries that
+        // fail re-verification are kept in the output, just rejected rather
+        // than reconciled, so they don't silently vanish from the JSON.
+        let mut verified_reconcile_entries = Vec::with_capacity(reconcile_entries.len());
+        for accel in reconcile_entries {
+            let authorized = accel.signature.as_deref().is_some_and(|signature| {
+                authorizer.verify(
+                    &accel.chain,
+                    accel.txid.as_deref().unwrap(),
+                    accel.fee_delta.unwrap(),
+                    accel.added,
+                    signature,
+                )
+            });
+            if authorized {
+                verified_reconcile_entries.push(accel);
+            } else {
+                reject(
+                    &mut sinks,
+                    &accel.chain,
+                    accel.txid.as_deref().unwrap(),
+                    accel.fee_delta.unwrap(),
+                );
+                output.push(accel);
+            }
+        }
 
-    if !had_failure {
-        data.accelerations = remaining_accelerations;
-        let updated_json = serde_json::to_string_pretty(&data)?;
-        fs::write(json_path, updated_json)?;
+        for accel in freshly_applied.into_iter().chain(verified_reconcile_entries) {
+            match reconcile(&client, &chain, &accel, &mut sinks) {
+                Reconciliation::Pruned => {}
+                Reconciliation::Kept => output.push(accel),
+            }
+        }
     }
 
+    data.accelerations = output;
+    let updated_json = serde_json::to_string_pretty(&data)?;
+    fs::write(json_path, updated_json)?;
+
     Ok(())
 }
 
+fn mark_failed(accel: &mut Acceleration, now: DateTime<Utc>) {
+    accel.attempts += 1;
+    accel.status = Status::Failed;
+    accel.next_retry_at = Some(now + backoff_after(accel.attempts));
+}
+
 fn get_timestamp() -> String {
     let now = SystemTime::now();
     let datetime: DateTime<Utc> = now.into();
     datetime.format("%Y-%m-%d %H:%M:%S UTC").to_string()
 }
+
+/// Builds an `AccelerationEvent` and fans it out to every configured sink.
+fn emit(
+    sinks: &mut [Box<dyn Sink>],
+    chain: &str,
+    txid: &str,
+    fee_delta: i64,
+    error: Option<String>,
+) {
+    let event = AccelerationEvent {
+        timestamp: get_timestamp(),
+        chain: chain.to_string(),
+        txid: txid.to_string(),
+        fee_delta,
+        status: if error.is_some() {
+            EventStatus::Failed
+        } else {
+            EventStatus::Success
+        },
+        error,
+    };
+    for sink in sinks.iter_mut() {
+        sink.emit(&event);
+    }
+}
+
+enum Reconciliation {
+    /// The txid is no longer in the mempool (mined or evicted): drop it.
+    Pruned,
+    /// Still in the mempool, whether or not a corrective bump was needed.
+    Kept,
+}
+
+/// Caps a corrective fee bump at the `feeDelta` the caller actually signed
+/// for. `effectiveFee`/`effectiveVsize` are not covered by the BIP340
+/// signature (src/auth.rs), so a signer could sign a trivial `feeDelta` while
+/// setting an arbitrarily large `effectiveFee` to drive an oversized,
+/// effectively-unauthorized correction; each correction is therefore capped
+/// at the signed delta. The cap applies per call only: the original delta
+/// plus one correction can reach twice the signed delta, and an applied entry
+/// still in the mempool is reconciled again on every run, so corrections can
+/// repeat until the target is met.
+fn bounded_shortfall(target_sats: i64, modified_sats: i64, signed_fee_delta: i64) -> i64 {
+    let shortfall = target_sats - modified_sats;
+    shortfall.clamp(0, signed_fee_delta.max(0))
+}
+
+/// After a successful `prioritisetransaction`, confirms the delta actually
+/// raised the transaction's effective fee rate by reading back its mempool
+/// entry, and issues a corrective bump if it fell short.
+fn reconcile(
+    client: &RpcClient,
+    chain: &str,
+    accel: &Acceleration,
+    sinks: &mut [Box<dyn Sink>],
+) -> Reconciliation {
+    let txid = accel.txid.as_deref().unwrap();
+
+    let entry = match client.call("getmempoolentry", json!([txid])) {
+        Ok(entry) => entry,
+        Err(RpcCallError::Rpc(e)) if e.code == -5 => {
+            // No longer in the mempool: already mined or evicted, so the
+            // acceleration is resolved one way or the other.
+            return Reconciliation::Pruned;
+        }
+        Err(e) => {
+            emit(
+                sinks,
+                chain,
+                txid,
+                accel.fee_delta.unwrap_or(0),
+                Some(format!("post-apply mempool check failed: {e}")),
+            );
+            return Reconciliation::Kept;
+        }
+    };
+
+    if accel.effective_vsize == 0 {
+        return Reconciliation::Kept;
+    }
+
+    let modified_btc = entry
+        .get("fees")
+        .and_then(|fees| fees.get("modified"))
+        .and_then(|v| v.as_f64())
+        .or_else(|| entry.get("descendantfees").and_then(|v| v.as_f64()).map(|sat| sat / 1e8))
+        .unwrap_or(0.0);
+    let modified_sats = (modified_btc * 1e8).round() as i64;
+
+    let target_sats = accel.effective_fee as i64;
+    let shortfall = bounded_shortfall(target_sats, modified_sats, accel.fee_delta.unwrap_or(0));
+    if shortfall <= 0 {
+        return Reconciliation::Kept;
+    }
+
+    match client.call("prioritisetransaction", json!([txid, 0.0, shortfall])) {
+        Ok(_) => emit(sinks, chain, txid, shortfall, None),
+        Err(e) => emit(
+            sinks,
+            chain,
+            txid,
+            shortfall,
+            Some(format!("corrective prioritisetransaction failed: {e}")),
+        ),
+    }
+    Reconciliation::Kept
+}
+
+/// Records an entry that was never sent to the node because it was unsigned
+/// or failed BIP340 verification.
+fn reject(sinks: &mut [Box<dyn Sink>], chain: &str, txid: &str, fee_delta: i64) {
+    let event = AccelerationEvent {
+        timestamp: get_timestamp(),
+        chain: chain.to_string(),
+        txid: txid.to_string(),
+        fee_delta,
+        status: EventStatus::Rejected,
+        error: Some("missing or invalid signature".to_string()),
+    };
+    for sink in sinks.iter_mut() {
+        sink.emit(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_shortfall_is_zero_when_target_already_met() {
+        assert_eq!(bounded_shortfall(1_000, 1_000, 500), 0);
+        assert_eq!(bounded_shortfall(1_000, 1_500, 500), 0);
+    }
+
+    #[test]
+    fn bounded_shortfall_caps_at_the_signed_fee_delta() {
+        // Signed for a 100-sat bump, but `effectiveFee` claims a 10,000-sat
+        // target: the correction must not exceed what was actually signed.
+        assert_eq!(bounded_shortfall(10_000, 0, 100), 100);
+    }
+
+    #[test]
+    fn bounded_shortfall_never_goes_negative_for_a_negative_signed_delta() {
+        assert_eq!(bounded_shortfall(10_000, 0, -50), 0);
+    }
+
+    #[test]
+    fn backoff_after_grows_exponentially_and_caps() {
+        let first = backoff_after(1);
+        let second = backoff_after(2);
+        assert_eq!(first, Duration::seconds(BASE_BACKOFF_SECS));
+        assert_eq!(second, Duration::seconds(BASE_BACKOFF_SECS * 2));
+        assert_eq!(backoff_after(20), Duration::seconds(MAX_BACKOFF_SECS));
+    }
+}
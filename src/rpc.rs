@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+/// An unresponsive node must not hang a run indefinitely: cron/systemd-timer
+/// invocations need to reach the point where status/backoff gets persisted
+/// even when the node is down.
+const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Connection details for a single Bitcoin Core (or compatible) RPC endpoint.
+#[derive(Debug, Clone)]
+pub struct RpcConfig {
+    pub host: String,
+    pub port: u16,
+    pub wallet: Option<String>,
+    pub auth: RpcAuth,
+}
+
+/// How we authenticate against the node's HTTP RPC interface.
+#[derive(Debug, Clone)]
+pub enum RpcAuth {
+    /// `user:password` read straight from the node's `.cookie` file.
+    CookieFile(String),
+    /// Statically configured `rpcuser`/`rpcpassword`.
+    UserPass(String, String),
+}
+
+impl RpcConfig {
+    /// Builds a config from the environment, falling back to `bitcoind`'s
+    /// mainnet RPC port and the `cormorant` wallet.
+    ///
+    /// - `RPC_HOST` (default `127.0.0.1`)
+    /// - `RPC_PORT` (default `8332`)
+    /// - `RPC_WALLET` (default `cormorant`)
+    /// - `RPC_COOKIE_FILE` takes priority over `RPC_USER`/`RPC_PASSWORD`
+    pub fn from_env() -> io::Result<Self> {
+        let host = std::env::var("RPC_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+        let port = std::env::var("RPC_PORT")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(8332);
+        let wallet = std::env::var("RPC_WALLET")
+            .ok()
+            .or_else(|| Some("cormorant".to_string()));
+
+        let auth = if let Ok(cookie_path) = std::env::var("RPC_COOKIE_FILE") {
+            RpcAuth::CookieFile(fs::read_to_string(cookie_path)?.trim().to_string())
+        } else {
+            let user = std::env::var("RPC_USER").unwrap_or_default();
+            let password = std::env::var("RPC_PASSWORD").unwrap_or_default();
+            RpcAuth::UserPass(user, password)
+        };
+
+        Ok(Self {
+            host,
+            port,
+            wallet,
+            auth,
+        })
+    }
+
+    fn url(&self) -> String {
+        match &self.wallet {
+            Some(wallet) => format!("http://{}:{}/wallet/{}", self.host, self.port, wallet),
+            None => format!("http://{}:{}/", self.host, self.port),
+        }
+    }
+
+    fn basic_auth(&self) -> (String, String) {
+        match &self.auth {
+            RpcAuth::CookieFile(cookie) => match cookie.split_once(':') {
+                Some((user, pass)) => (user.to_string(), pass.to_string()),
+                None => (cookie.clone(), String::new()),
+            },
+            RpcAuth::UserPass(user, pass) => (user.clone(), pass.clone()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RpcRequest<'a> {
+    jsonrpc: &'a str,
+    id: u64,
+    method: &'a str,
+    params: Value,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RpcResponse {
+    pub id: u64,
+    pub result: Option<Value>,
+    pub error: Option<RpcErrorObject>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct RpcErrorObject {
+    pub code: i64,
+    pub message: String,
+}
+
+impl fmt::Display for RpcErrorObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RPC error {}: {}", self.code, self.message)
+    }
+}
+
+/// Errors that can occur while talking to the node, as distinct from errors
+/// the node itself reports for a given call.
+#[derive(Debug)]
+pub enum RpcCallError {
+    Transport(reqwest::Error),
+    Rpc(RpcErrorObject),
+    InvalidResponse(String),
+}
+
+impl fmt::Display for RpcCallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RpcCallError::Transport(e) => write!(f, "transport error: {e}"),
+            RpcCallError::Rpc(e) => write!(f, "{e}"),
+            RpcCallError::InvalidResponse(msg) => write!(f, "invalid RPC response: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for RpcCallError {}
+
+impl From<reqwest::Error> for RpcCallError {
+    fn from(e: reqwest::Error) -> Self {
+        RpcCallError::Transport(e)
+    }
+}
+
+/// A minimal Bitcoin Core JSON-RPC client: HTTP POST with basic auth, one
+/// request per call or a batch of requests per call.
+pub struct RpcClient {
+    config: RpcConfig,
+    http: reqwest::blocking::Client,
+}
+
+impl RpcClient {
+    pub fn new(config: RpcConfig) -> Self {
+        Self {
+            config,
+            http: reqwest::blocking::Client::builder()
+                .timeout(RPC_TIMEOUT)
+                .build()
+                .expect("RPC client configuration is static and always buildable"),
+        }
+    }
+
+    /// Calls a single RPC method and returns its `result`, or the node's own
+    /// `error` object if the call failed at the RPC layer.
+    pub fn call(&self, method: &str, params: Value) -> Result<Value, RpcCallError> {
+        let responses = self.call_batch(&[(method.to_string(), params)])?;
+        let response = responses
+            .into_iter()
+            .next()
+            .ok_or_else(|| RpcCallError::InvalidResponse("empty batch response".to_string()))?;
+        match response.error {
+            Some(err) => Err(RpcCallError::Rpc(err)),
+            None => response
+                .result
+                .ok_or_else(|| RpcCallError::InvalidResponse("missing result".to_string())),
+        }
+    }
+
+    /// Sends several RPC calls as a single JSON-RPC batch request, returning
+    /// one response per call in the same order they were given.
+    pub fn call_batch(
+        &self,
+        calls: &[(String, Value)],
+    ) -> Result<Vec<RpcResponse>, RpcCallError> {
+        let body: Vec<RpcRequest> = calls
+            .iter()
+            .enumerate()
+            .map(|(id, (method, params))| RpcRequest {
+                jsonrpc: "2.0",
+                id: id as u64,
+                method,
+                params: params.clone(),
+            })
+            .collect();
+
+        let (user, password) = self.config.basic_auth();
+        let response = self
+            .http
+            .post(self.config.url())
+            .basic_auth(user, Some(password))
+            .json(&body)
+            .send()?;
+
+        let responses: Vec<RpcResponse> = response.json()?;
+        order_responses(responses, calls.len())
+    }
+}
+
+/// Sorts a batch response into call order and checks it actually has one
+/// response per call with ids `0..calls.len()` — a short, duplicated-id, or
+/// gapped batch from a misbehaving node/proxy is an `InvalidResponse` error
+/// for the whole batch rather than silently truncated by `zip`.
+fn order_responses(
+    mut responses: Vec<RpcResponse>,
+    expected: usize,
+) -> Result<Vec<RpcResponse>, RpcCallError> {
+    if responses.len() != expected {
+        return Err(RpcCallError::InvalidResponse(format!(
+            "expected {expected} batch responses, got {}",
+            responses.len()
+        )));
+    }
+
+    responses.sort_by_key(|r| r.id);
+
+    for (position, response) in responses.iter().enumerate() {
+        if response.id != position as u64 {
+            return Err(RpcCallError::InvalidResponse(format!(
+                "batch response ids are not a contiguous 0..{expected} set (found id {} at position {position})",
+                response.id
+            )));
+        }
+    }
+
+    Ok(responses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(id: u64) -> RpcResponse {
+        RpcResponse {
+            id,
+            result: Some(Value::Bool(true)),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn orders_a_well_formed_batch() {
+        let responses = vec![response(2), response(0), response(1)];
+        let ordered = order_responses(responses, 3).unwrap();
+        let ids: Vec<u64> = ordered.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn rejects_a_short_batch() {
+        let responses = vec![response(0), response(1)];
+        assert!(order_responses(responses, 3).is_err());
+    }
+
+    #[test]
+    fn rejects_duplicated_ids() {
+        let responses = vec![response(0), response(0), response(2)];
+        assert!(order_responses(responses, 3).is_err());
+    }
+
+    #[test]
+    fn rejects_a_gapped_id_set() {
+        let responses = vec![response(0), response(1), response(5)];
+        assert!(order_responses(responses, 3).is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::time::Duration;
+
+/// A stuck webhook endpoint must not hang a run indefinitely.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventStatus {
+    Success,
+    Failed,
+    /// Unsigned or failed signature verification; never reached the node.
+    Rejected,
+}
+
+/// A single acceleration outcome, structured so every sink can render it the
+/// same way whether it's a log line, an NDJSON record, or a webhook body.
+#[derive(Serialize, Debug, Clone)]
+pub struct AccelerationEvent {
+    pub timestamp: String,
+    pub chain: String,
+    pub txid: String,
+    #[serde(rename = "feeDelta")]
+    pub fee_delta: i64,
+    pub status: EventStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Destination for acceleration events. Implementations should not let a
+/// delivery failure stop the run; they log their own errors to stderr.
+pub trait Sink {
+    fn emit(&mut self, event: &AccelerationEvent);
+}
+
+/// The original behavior: one free-text line per event, appended to a file.
+pub struct AppendLogSink {
+    file: File,
+}
+
+impl AppendLogSink {
+    pub fn new(path: &str) -> io::Result<Self> {
+        Ok(Self {
+            file: File::options().create(true).append(true).open(path)?,
+        })
+    }
+}
+
+impl Sink for AppendLogSink {
+    fn emit(&mut self, event: &AccelerationEvent) {
+        let label = match event.status {
+            EventStatus::Success => "Success",
+            EventStatus::Failed => "Failed",
+            EventStatus::Rejected => "Rejected",
+        };
+        let line = match &event.error {
+            Some(error) => format!(
+                "{}: {} - chain: {}, txid: {}, fee_delta: {}, error: {}\n",
+                event.timestamp, label, event.chain, event.txid, event.fee_delta, error
+            ),
+            None => format!(
+                "{}: {} - chain: {}, txid: {}, fee_delta: {}\n",
+                event.timestamp, label, event.chain, event.txid, event.fee_delta
+            ),
+        };
+        if let Err(e) = self.file.write_all(line.as_bytes()) {
+            eprintln!("append-log sink: failed to write: {e}");
+        }
+    }
+}
+
+/// Writes one JSON object per line to stdout, for piping into `jq` or a log
+/// shipper.
+pub struct StdoutNdjsonSink;
+
+impl Sink for StdoutNdjsonSink {
+    fn emit(&mut self, event: &AccelerationEvent) {
+        match serde_json::to_string(event) {
+            Ok(line) => println!("{line}"),
+            Err(e) => eprintln!("stdout-ndjson sink: failed to serialize event: {e}"),
+        }
+    }
+}
+
+/// Writes one JSON object per line to a configured file, independent of the
+/// free-text append-log.
+pub struct FileSink {
+    file: File,
+}
+
+impl FileSink {
+    pub fn new(path: &str) -> io::Result<Self> {
+        Ok(Self {
+            file: File::options().create(true).append(true).open(path)?,
+        })
+    }
+}
+
+impl Sink for FileSink {
+    fn emit(&mut self, event: &AccelerationEvent) {
+        let line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("file sink: failed to serialize event: {e}");
+                return;
+            }
+        };
+        if let Err(e) = writeln!(self.file, "{line}") {
+            eprintln!("file sink: failed to write: {e}");
+        }
+    }
+}
+
+/// POSTs each event as a JSON body to a configured HTTP endpoint, e.g. a
+/// monitoring or alerting webhook.
+pub struct WebhookSink {
+    url: String,
+    http: reqwest::blocking::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            http: reqwest::blocking::Client::builder()
+                .timeout(WEBHOOK_TIMEOUT)
+                .build()
+                .expect("webhook client configuration is static and always buildable"),
+        }
+    }
+}
+
+impl Sink for WebhookSink {
+    fn emit(&mut self, event: &AccelerationEvent) {
+        if let Err(e) = self.http.post(&self.url).json(event).send() {
+            eprintln!("webhook sink: failed to deliver event to {}: {e}", self.url);
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SinkSpec {
+    AppendLog { path: String },
+    StdoutNdjson,
+    File { path: String },
+    Webhook { url: String },
+}
+
+const DEFAULT_SINKS_PATH: &str = "sinks.json";
+const DEFAULT_LOG_PATH: &str = "results.log";
+
+/// Parses `sinks.json`'s contents into the list of configured sinks, or
+/// falls back to the original single append-log sink when `raw` is `None`
+/// (the file doesn't exist).
+fn parse_sink_specs(raw: Option<&str>) -> io::Result<Vec<SinkSpec>> {
+    match raw {
+        Some(contents) => Ok(serde_json::from_str(contents)?),
+        None => Ok(vec![SinkSpec::AppendLog {
+            path: DEFAULT_LOG_PATH.to_string(),
+        }]),
+    }
+}
+
+/// Loads the list of sinks to fan acceleration events out to from
+/// `sinks.json`. Falls back to the original single append-log sink when no
+/// config file is present.
+pub fn load_sinks() -> io::Result<Vec<Box<dyn Sink>>> {
+    let raw = match fs::read_to_string(DEFAULT_SINKS_PATH) {
+        Ok(contents) => Some(contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e),
+    };
+    let specs = parse_sink_specs(raw.as_deref())?;
+
+    let mut sinks: Vec<Box<dyn Sink>> = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let sink: Box<dyn Sink> = match spec {
+            SinkSpec::AppendLog { path } => Box::new(AppendLogSink::new(&path)?),
+            SinkSpec::StdoutNdjson => Box::new(StdoutNdjsonSink),
+            SinkSpec::File { path } => Box::new(FileSink::new(&path)?),
+            SinkSpec::Webhook { url } => Box::new(WebhookSink::new(url)),
+        };
+        sinks.push(sink);
+    }
+    Ok(sinks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!(
+            "trustless_accelerator_test_{}_{}_{name}",
+            std::process::id(),
+            name.len()
+        ))
+    }
+
+    fn sample_event(status: EventStatus, error: Option<&str>) -> AccelerationEvent {
+        AccelerationEvent {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            chain: "bitcoin".to_string(),
+            txid: "deadbeef".to_string(),
+            fee_delta: 500,
+            status,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn parses_custom_sink_specs_from_json() {
+        let raw = r#"[
+            {"type": "append_log", "path": "a.log"},
+            {"type": "stdout_ndjson"},
+            {"type": "file", "path": "b.jsonl"},
+            {"type": "webhook", "url": "https://example.com/hook"}
+        ]"#;
+        let specs = parse_sink_specs(Some(raw)).unwrap();
+        assert_eq!(
+            specs,
+            vec![
+                SinkSpec::AppendLog {
+                    path: "a.log".to_string()
+                },
+                SinkSpec::StdoutNdjson,
+                SinkSpec::File {
+                    path: "b.jsonl".to_string()
+                },
+                SinkSpec::Webhook {
+                    url: "https://example.com/hook".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_append_log_when_no_config_present() {
+        let specs = parse_sink_specs(None).unwrap();
+        assert_eq!(
+            specs,
+            vec![SinkSpec::AppendLog {
+                path: DEFAULT_LOG_PATH.to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_sink_spec_json() {
+        assert!(parse_sink_specs(Some("not json")).is_err());
+    }
+
+    #[test]
+    fn append_log_sink_writes_one_readable_line_per_event() {
+        let path = temp_path("append_log");
+        let path_str = path.to_str().unwrap();
+        {
+            let mut sink = AppendLogSink::new(path_str).unwrap();
+            sink.emit(&sample_event(EventStatus::Success, None));
+            sink.emit(&sample_event(EventStatus::Failed, Some("boom")));
+        }
+        let contents = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("Success - chain: bitcoin, txid: deadbeef, fee_delta: 500"));
+        assert!(lines[1].contains("Failed - chain: bitcoin, txid: deadbeef, fee_delta: 500, error: boom"));
+    }
+
+    #[test]
+    fn file_sink_writes_one_json_line_per_event() {
+        let path = temp_path("file_sink");
+        let path_str = path.to_str().unwrap();
+        {
+            let mut sink = FileSink::new(path_str).unwrap();
+            sink.emit(&sample_event(EventStatus::Rejected, Some("missing or invalid signature")));
+        }
+        let contents = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let parsed: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(parsed["status"], "rejected");
+        assert_eq!(parsed["txid"], "deadbeef");
+        assert_eq!(parsed["feeDelta"], 500);
+        assert_eq!(parsed["error"], "missing or invalid signature");
+    }
+}